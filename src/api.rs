@@ -0,0 +1,110 @@
+// Central API dispatcher: every supported (api_key, api_version) pair registers a
+// `KafkaApi` handler here instead of `send_response` hand-matching on api_key and
+// hardcoding version checks.
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::api_versions::ApiVersionsResponse;
+use crate::codec::{Request, Response};
+use crate::context::Context;
+use crate::describe_topic_partitions::DescribeTopicPartitionsResponse;
+use crate::error_codes;
+use crate::group::{FindCoordinatorResponse, OffsetCommitResponse, OffsetFetchResponse};
+use crate::produce::ProduceResponse;
+
+// One variant per supported api_key. Each inner type owns the data it needs to render
+// its own body and knows how to write it (see `write` below).
+pub enum ApiResponse {
+    ApiVersions(ApiVersionsResponse),
+    Produce(ProduceResponse),
+    FindCoordinator(FindCoordinatorResponse),
+    OffsetCommit(OffsetCommitResponse),
+    OffsetFetch(OffsetFetchResponse),
+    DescribeTopicPartitions(DescribeTopicPartitionsResponse),
+}
+
+impl ApiResponse {
+    pub fn write(&self, buf: &mut Vec<u8>) {
+        match self {
+            ApiResponse::ApiVersions(r) => r.write(buf),
+            ApiResponse::Produce(r) => r.write(buf),
+            ApiResponse::FindCoordinator(r) => r.write(buf),
+            ApiResponse::OffsetCommit(r) => r.write(buf),
+            ApiResponse::OffsetFetch(r) => r.write(buf),
+            ApiResponse::DescribeTopicPartitions(r) => r.write(buf),
+        }
+    }
+
+    // ApiVersions is the one response that must stay on response header v0 (no tag
+    // buffer after correlation_id) even though its body is otherwise flexible: clients
+    // rely on it to negotiate a version before they know whether we speak flexible
+    // headers at all. Every other response here only exists in its flexible form.
+    pub fn flexible_header(&self) -> bool {
+        !matches!(self, ApiResponse::ApiVersions(_))
+    }
+}
+
+pub trait KafkaApi: Send + Sync {
+    fn api_key(&self) -> i16;
+    fn min_version(&self) -> i16;
+    fn max_version(&self) -> i16;
+    fn handle(&self, request: &Request, ctx: &Context) -> ApiResponse;
+}
+
+pub struct ApiRegistry {
+    apis: HashMap<(i16, i16), Arc<dyn KafkaApi>>,
+}
+
+impl ApiRegistry {
+    pub fn new() -> Self {
+        let handlers: Vec<Arc<dyn KafkaApi>> = vec![
+            Arc::new(crate::api_versions::ApiVersionsApi),
+            Arc::new(crate::produce::ProduceApi),
+            Arc::new(crate::group::FindCoordinatorApi),
+            Arc::new(crate::group::OffsetCommitApi),
+            Arc::new(crate::group::OffsetFetchApi),
+            Arc::new(crate::describe_topic_partitions::DescribeTopicPartitionsApi),
+        ];
+
+        let mut apis = HashMap::new();
+        for handler in handlers {
+            for version in handler.min_version()..=handler.max_version() {
+                apis.insert((handler.api_key(), version), handler.clone());
+            }
+        }
+
+        ApiRegistry { apis }
+    }
+
+    pub fn dispatch(&self, request: &Request, ctx: &Context) -> Response {
+        let header = &request.header;
+
+        match self.apis.get(&(header.api_key, header.api_version)) {
+            Some(api) => {
+                let response = api.handle(request, ctx);
+                let mut body = Vec::new();
+                response.write(&mut body);
+                Response::new(header.correlation_id, body, response.flexible_header())
+            }
+            None => {
+                println!(
+                    "no handler for api_key {} version {}",
+                    header.api_key, header.api_version
+                );
+                let body = unsupported_version_body(header.api_key);
+                Response::new(header.correlation_id, body, false)
+            }
+        }
+    }
+}
+
+// Most responses don't carry a top-level error code, so there's nothing generic we can
+// put in their body for an unsupported version. ApiVersions is the one API whose body
+// starts with an error code specifically so clients can renegotiate a version.
+fn unsupported_version_body(api_key: i16) -> Vec<u8> {
+    if api_key == crate::api_versions::API_KEY {
+        error_codes::UNSUPPORTED_VERSION.to_be_bytes().to_vec()
+    } else {
+        Vec::new()
+    }
+}
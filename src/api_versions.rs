@@ -0,0 +1,107 @@
+// ApiVersions (api_key 18): tells the client which (api_key, version range) pairs this
+// broker understands. Kept in its own module now that it's one handler among several
+// instead of the only thing `send_response` knew how to build.
+use crate::api::{ApiResponse, KafkaApi};
+use crate::codec::Request;
+use crate::context::Context;
+use crate::error_codes;
+
+pub const API_KEY: i16 = 18;
+
+struct SupportedApi {
+    api_key: i16,
+    min_version: i16,
+    max_version: i16,
+}
+
+// Kept in lockstep with each handler's own min_version()/max_version(): only the exact
+// version a handler implements is advertised, so a client that negotiates off this list
+// can never land on a version the dispatcher will reject.
+const SUPPORTED_APIS: &[SupportedApi] = &[
+    SupportedApi {
+        api_key: 0,
+        min_version: 9,
+        max_version: 9,
+    },
+    SupportedApi {
+        api_key: 8,
+        min_version: 8,
+        max_version: 8,
+    },
+    SupportedApi {
+        api_key: 9,
+        min_version: 6,
+        max_version: 6,
+    },
+    SupportedApi {
+        api_key: 10,
+        min_version: 3,
+        max_version: 3,
+    },
+    SupportedApi {
+        api_key: 18,
+        min_version: 0,
+        max_version: 4,
+    },
+    SupportedApi {
+        api_key: 75,
+        min_version: 0,
+        max_version: 0,
+    },
+];
+
+// Whether the body uses compact arrays/tagged fields (v3+) or the plain encoding every
+// version before that used. This is independent of the response *header*'s flexibility:
+// ApiVersions keeps header v0 at every version (see ApiResponse::flexible_header), but its
+// body follows the normal flexible-versions cutover like any other API.
+pub struct ApiVersionsResponse {
+    pub flexible_body: bool,
+}
+
+impl ApiVersionsResponse {
+    pub fn write(&self, body: &mut Vec<u8>) {
+        body.extend_from_slice(&error_codes::NONE.to_be_bytes());
+
+        if self.flexible_body {
+            body.push((SUPPORTED_APIS.len() + 1) as u8); // compact array length + 1
+        } else {
+            body.extend_from_slice(&(SUPPORTED_APIS.len() as i32).to_be_bytes());
+        }
+        for api in SUPPORTED_APIS {
+            body.extend_from_slice(&api.api_key.to_be_bytes());
+            body.extend_from_slice(&api.min_version.to_be_bytes());
+            body.extend_from_slice(&api.max_version.to_be_bytes());
+            if self.flexible_body {
+                body.push(0); // tagged fields
+            }
+        }
+
+        body.extend_from_slice(&0i32.to_be_bytes()); // throttle_time_ms
+
+        if self.flexible_body {
+            body.push(0); // tagged fields
+        }
+    }
+}
+
+pub struct ApiVersionsApi;
+
+impl KafkaApi for ApiVersionsApi {
+    fn api_key(&self) -> i16 {
+        API_KEY
+    }
+
+    fn min_version(&self) -> i16 {
+        0
+    }
+
+    fn max_version(&self) -> i16 {
+        4
+    }
+
+    fn handle(&self, request: &Request, _ctx: &Context) -> ApiResponse {
+        ApiResponse::ApiVersions(ApiVersionsResponse {
+            flexible_body: request.header.api_version >= 3,
+        })
+    }
+}
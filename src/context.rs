@@ -0,0 +1,10 @@
+// Shared state every connection's API handlers can reach into.
+use std::sync::Arc;
+
+use crate::group::OffsetStore;
+use crate::metadata::MetadataStore;
+
+pub struct Context {
+    pub metadata: Arc<dyn MetadataStore>,
+    pub offsets: Arc<dyn OffsetStore>,
+}
@@ -0,0 +1,37 @@
+// CRC32C (Castagnoli) checksum, used to validate Produce record batches. Implemented as a
+// plain table-driven CRC so we don't need an external crate just for this one check.
+const POLY: u32 = 0x82f63b78; // Reversed Castagnoli polynomial
+
+fn build_table() -> [u32; 256] {
+    let mut table = [0u32; 256];
+    let mut byte = 0u32;
+
+    while byte < 256 {
+        let mut crc = byte;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ POLY
+            } else {
+                crc >> 1
+            };
+            bit += 1;
+        }
+        table[byte as usize] = crc;
+        byte += 1;
+    }
+
+    table
+}
+
+pub fn crc32c(data: &[u8]) -> u32 {
+    let table = build_table();
+    let mut crc = 0xffff_ffffu32;
+
+    for &byte in data {
+        let index = ((crc ^ byte as u32) & 0xff) as usize;
+        crc = (crc >> 8) ^ table[index];
+    }
+
+    crc ^ 0xffff_ffff
+}
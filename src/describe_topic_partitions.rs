@@ -0,0 +1,194 @@
+// DescribeTopicPartitions (api_key 75): the only metadata request implemented so far.
+// Always flexible (KIP-482), so every string/array in the wire format is compact.
+use std::io::Cursor;
+
+use crate::api::{ApiResponse, KafkaApi};
+use crate::codec::Request;
+use crate::context::Context;
+use crate::error::KafkaError;
+use crate::error_codes;
+use crate::metadata::MetadataStore;
+use crate::readers::{
+    read_compact_array, read_compact_string, read_int32, read_tagged_fields, write_compact_string,
+};
+
+pub struct DescribeTopicPartitionsRequest {
+    pub topic_names: Vec<String>,
+    pub response_partition_limit: i32,
+    pub cursor: Option<(String, i32)>,
+}
+
+pub fn parse_request(body: &[u8]) -> Result<DescribeTopicPartitionsRequest, KafkaError> {
+    let mut cursor = Cursor::new(body);
+
+    let topic_names = read_compact_array(&mut cursor, |cursor| {
+        let name = read_compact_string(cursor)?;
+        read_tagged_fields(cursor)?;
+        Ok(name)
+    })?;
+
+    let response_partition_limit = read_int32(&mut cursor)?;
+
+    // The cursor is a nullable struct encoded as a single marker byte, not a varint:
+    // `0xff` means null, anything else means a cursor struct follows.
+    let mut cursor_marker = [0u8; 1];
+    std::io::Read::read_exact(&mut cursor, &mut cursor_marker)?;
+    let has_cursor = cursor_marker[0] != 0xff;
+    let request_cursor = if has_cursor {
+        let name = read_compact_string(&mut cursor)?;
+        let partition_index = read_int32(&mut cursor)?;
+        read_tagged_fields(&mut cursor)?;
+        Some((name, partition_index))
+    } else {
+        None
+    };
+
+    read_tagged_fields(&mut cursor)?;
+
+    Ok(DescribeTopicPartitionsRequest {
+        topic_names,
+        response_partition_limit,
+        cursor: request_cursor,
+    })
+}
+
+pub struct PartitionResult {
+    pub error_code: i16,
+    pub index: i32,
+    pub leader_id: i32,
+    pub leader_epoch: i32,
+    pub replica_nodes: Vec<i32>,
+    pub isr_nodes: Vec<i32>,
+    pub eligible_leader_replicas: Vec<i32>,
+}
+
+pub struct TopicResult {
+    pub error_code: i16,
+    pub name: String,
+    pub uuid: [u8; 16],
+    pub is_internal: bool,
+    pub partitions: Vec<PartitionResult>,
+}
+
+pub struct DescribeTopicPartitionsResponse {
+    pub topics: Vec<TopicResult>,
+}
+
+impl DescribeTopicPartitionsResponse {
+    pub fn write(&self, body: &mut Vec<u8>) {
+        body.extend_from_slice(&0i32.to_be_bytes()); // throttle_time_ms
+
+        body.push((self.topics.len() + 1) as u8);
+        for topic in &self.topics {
+            body.extend_from_slice(&topic.error_code.to_be_bytes());
+            write_compact_string(body, &topic.name);
+            body.extend_from_slice(&topic.uuid);
+            body.push(topic.is_internal as u8);
+
+            body.push((topic.partitions.len() + 1) as u8);
+            for partition in &topic.partitions {
+                body.extend_from_slice(&partition.error_code.to_be_bytes());
+                body.extend_from_slice(&partition.index.to_be_bytes());
+                body.extend_from_slice(&partition.leader_id.to_be_bytes());
+                body.extend_from_slice(&partition.leader_epoch.to_be_bytes());
+                write_compact_i32_array(body, &partition.replica_nodes);
+                write_compact_i32_array(body, &partition.isr_nodes);
+                write_compact_i32_array(body, &partition.eligible_leader_replicas);
+                write_compact_i32_array(body, &[]); // last_known_elr: not tracked
+                write_compact_i32_array(body, &[]); // offline_replicas: not tracked
+                body.push(0); // tagged fields
+            }
+
+            body.extend_from_slice(&0i32.to_be_bytes()); // topic_authorized_operations
+            body.push(0); // tagged fields
+        }
+
+        body.push(0xff); // next_cursor: null, we never paginate yet
+        body.push(0); // tagged fields
+    }
+}
+
+pub fn build_response(
+    request: &DescribeTopicPartitionsRequest,
+    store: &dyn MetadataStore,
+) -> DescribeTopicPartitionsResponse {
+    let topics = request
+        .topic_names
+        .iter()
+        .map(|name| match store.topic(name) {
+            Some(topic) => known_topic_result(&topic),
+            None => unknown_topic_result(name),
+        })
+        .collect();
+
+    DescribeTopicPartitionsResponse { topics }
+}
+
+fn known_topic_result(topic: &crate::metadata::TopicMetadata) -> TopicResult {
+    let partitions = topic
+        .partitions
+        .iter()
+        .map(|partition| PartitionResult {
+            error_code: error_codes::NONE,
+            index: partition.index,
+            leader_id: partition.leader_id,
+            leader_epoch: partition.leader_epoch,
+            replica_nodes: partition.replica_nodes.clone(),
+            isr_nodes: partition.isr_nodes.clone(),
+            eligible_leader_replicas: partition.eligible_leader_replicas.clone(),
+        })
+        .collect();
+
+    TopicResult {
+        error_code: error_codes::NONE,
+        name: topic.name.clone(),
+        uuid: topic.uuid,
+        is_internal: topic.is_internal,
+        partitions,
+    }
+}
+
+fn unknown_topic_result(name: &str) -> TopicResult {
+    TopicResult {
+        error_code: error_codes::UNKNOWN_TOPIC_OR_PARTITION,
+        name: name.to_string(),
+        uuid: [0u8; 16], // all-zero for unknown topics
+        is_internal: false,
+        partitions: Vec::new(),
+    }
+}
+
+fn write_compact_i32_array(buf: &mut Vec<u8>, values: &[i32]) {
+    buf.push((values.len() + 1) as u8);
+    for value in values {
+        buf.extend_from_slice(&value.to_be_bytes());
+    }
+}
+
+pub struct DescribeTopicPartitionsApi;
+
+impl KafkaApi for DescribeTopicPartitionsApi {
+    fn api_key(&self) -> i16 {
+        75
+    }
+
+    fn min_version(&self) -> i16 {
+        0
+    }
+
+    fn max_version(&self) -> i16 {
+        0
+    }
+
+    fn handle(&self, request: &Request, ctx: &Context) -> ApiResponse {
+        let response = match parse_request(&request.body) {
+            Ok(dtp_request) => build_response(&dtp_request, ctx.metadata.as_ref()),
+            Err(e) => {
+                println!("failed to parse describe_topic_partitions request: {}", e);
+                DescribeTopicPartitionsResponse { topics: Vec::new() }
+            }
+        };
+
+        ApiResponse::DescribeTopicPartitions(response)
+    }
+}
@@ -0,0 +1,46 @@
+// Shared error type for everything that parses or serializes the Kafka wire format.
+use std::fmt;
+
+#[derive(Debug)]
+pub enum KafkaError {
+    Io(std::io::Error),
+    Utf8(std::string::FromUtf8Error),
+    InvalidStringLength(i16),
+    InvalidVarint,
+    CrcMismatch { expected: u32, computed: u32 },
+    UnsupportedRequest(&'static str),
+}
+
+impl fmt::Display for KafkaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            KafkaError::Io(e) => write!(f, "io error: {}", e),
+            KafkaError::Utf8(e) => write!(f, "invalid utf-8: {}", e),
+            KafkaError::InvalidStringLength(len) => write!(f, "invalid string length: {}", len),
+            KafkaError::InvalidVarint => write!(f, "varint is longer than its maximum encoded length"),
+            KafkaError::CrcMismatch { expected, computed } => {
+                write!(
+                    f,
+                    "crc32c mismatch: expected {}, computed {}",
+                    expected, computed
+                )
+            }
+            KafkaError::UnsupportedRequest(reason) => write!(f, "unsupported request: {}", reason),
+        }
+    }
+}
+
+impl std::error::Error for KafkaError {}
+
+// Required so `?` works inside Decoder::decode, which returns io::Result-like errors.
+impl From<std::io::Error> for KafkaError {
+    fn from(e: std::io::Error) -> Self {
+        KafkaError::Io(e)
+    }
+}
+
+impl From<std::string::FromUtf8Error> for KafkaError {
+    fn from(e: std::string::FromUtf8Error) -> Self {
+        KafkaError::Utf8(e)
+    }
+}
@@ -0,0 +1,6 @@
+// Kafka protocol error codes we know how to return. Not exhaustive, just the ones the
+// handlers implemented so far actually produce.
+pub const NONE: i16 = 0;
+pub const UNKNOWN_TOPIC_OR_PARTITION: i16 = 3;
+pub const CORRUPT_MESSAGE: i16 = 2;
+pub const UNSUPPORTED_VERSION: i16 = 35;
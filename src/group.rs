@@ -0,0 +1,423 @@
+// Consumer-group support: FindCoordinator (api_key 10), OffsetCommit (api_key 8) and
+// OffsetFetch (api_key 9). All three are only implemented in their flexible-header form.
+use std::collections::HashMap;
+use std::io::Cursor;
+use std::sync::Mutex;
+
+use crate::api::{ApiResponse, KafkaApi};
+use crate::codec::Request;
+use crate::context::Context;
+use crate::error::KafkaError;
+use crate::error_codes;
+use crate::readers::{
+    read_compact_array, read_compact_nullable_string, read_compact_string, read_int32, read_int64,
+    read_tagged_fields, write_compact_nullable_string, write_compact_string,
+};
+
+// Offsets are keyed by (group_id, topic, partition). A trait keeps the in-memory
+// implementation swappable for a durable backend later, same as MetadataStore.
+pub trait OffsetStore: Send + Sync {
+    fn commit(
+        &self,
+        group_id: &str,
+        topic: &str,
+        partition: i32,
+        offset: i64,
+        metadata: Option<String>,
+    );
+    fn fetch(&self, group_id: &str, topic: &str, partition: i32) -> Option<(i64, Option<String>)>;
+}
+
+#[derive(Default)]
+pub struct InMemoryOffsetStore {
+    offsets: Mutex<HashMap<(String, String, i32), (i64, Option<String>)>>,
+}
+
+impl OffsetStore for InMemoryOffsetStore {
+    fn commit(
+        &self,
+        group_id: &str,
+        topic: &str,
+        partition: i32,
+        offset: i64,
+        metadata: Option<String>,
+    ) {
+        let key = (group_id.to_string(), topic.to_string(), partition);
+        self.offsets.lock().unwrap().insert(key, (offset, metadata));
+    }
+
+    fn fetch(&self, group_id: &str, topic: &str, partition: i32) -> Option<(i64, Option<String>)> {
+        let key = (group_id.to_string(), topic.to_string(), partition);
+        self.offsets.lock().unwrap().get(&key).cloned()
+    }
+}
+
+// --- FindCoordinator -------------------------------------------------------------
+
+pub struct FindCoordinatorRequest {
+    pub key: String,
+}
+
+pub fn parse_find_coordinator_request(body: &[u8]) -> Result<FindCoordinatorRequest, KafkaError> {
+    let mut cursor = Cursor::new(body);
+    let key = read_compact_string(&mut cursor)?;
+    let _key_type = {
+        let mut buf = [0u8; 1];
+        std::io::Read::read_exact(&mut cursor, &mut buf)?;
+        buf[0]
+    };
+    read_tagged_fields(&mut cursor)?;
+    Ok(FindCoordinatorRequest { key })
+}
+
+pub struct FindCoordinatorResponse {
+    pub error_code: i16,
+    pub node_id: i32,
+    pub host: String,
+    pub port: i32,
+}
+
+impl FindCoordinatorResponse {
+    pub fn write(&self, body: &mut Vec<u8>) {
+        body.extend_from_slice(&0i32.to_be_bytes()); // throttle_time_ms
+        body.extend_from_slice(&self.error_code.to_be_bytes());
+        write_compact_nullable_string(body, None); // error_message
+        body.extend_from_slice(&self.node_id.to_be_bytes());
+        write_compact_string(body, &self.host);
+        body.extend_from_slice(&self.port.to_be_bytes());
+        body.push(0); // tagged fields
+    }
+}
+
+// We are the only broker in this cluster, so we always point clients back at ourselves.
+pub fn build_find_coordinator_response(
+    _request: &FindCoordinatorRequest,
+) -> FindCoordinatorResponse {
+    FindCoordinatorResponse {
+        error_code: error_codes::NONE,
+        node_id: 0,
+        host: "localhost".to_string(),
+        port: 9092,
+    }
+}
+
+// --- OffsetCommit -----------------------------------------------------------------
+
+pub struct OffsetCommitPartition {
+    pub partition_index: i32,
+    pub committed_offset: i64,
+    pub committed_metadata: Option<String>,
+}
+
+pub struct OffsetCommitTopic {
+    pub name: String,
+    pub partitions: Vec<OffsetCommitPartition>,
+}
+
+pub struct OffsetCommitRequest {
+    pub group_id: String,
+    pub topics: Vec<OffsetCommitTopic>,
+}
+
+pub fn parse_offset_commit_request(body: &[u8]) -> Result<OffsetCommitRequest, KafkaError> {
+    let mut cursor = Cursor::new(body);
+
+    let group_id = read_compact_string(&mut cursor)?;
+    let _generation_id_or_member_epoch = read_int32(&mut cursor)?;
+    let _member_id = read_compact_string(&mut cursor)?;
+    let _group_instance_id = read_compact_nullable_string(&mut cursor)?;
+
+    let topics = read_compact_array(&mut cursor, |cursor| {
+        let name = read_compact_string(cursor)?;
+        let partitions = read_compact_array(cursor, |cursor| {
+            let partition_index = read_int32(cursor)?;
+            let committed_offset = read_int64(cursor)?;
+            let _committed_leader_epoch = read_int32(cursor)?;
+            let committed_metadata = read_compact_nullable_string(cursor)?;
+            read_tagged_fields(cursor)?;
+            Ok(OffsetCommitPartition {
+                partition_index,
+                committed_offset,
+                committed_metadata,
+            })
+        })?;
+        read_tagged_fields(cursor)?;
+        Ok(OffsetCommitTopic { name, partitions })
+    })?;
+
+    read_tagged_fields(&mut cursor)?;
+
+    Ok(OffsetCommitRequest { group_id, topics })
+}
+
+pub struct OffsetCommitPartitionResult {
+    pub partition_index: i32,
+    pub error_code: i16,
+}
+
+pub struct OffsetCommitTopicResult {
+    pub name: String,
+    pub partitions: Vec<OffsetCommitPartitionResult>,
+}
+
+pub struct OffsetCommitResponse {
+    pub topics: Vec<OffsetCommitTopicResult>,
+}
+
+impl OffsetCommitResponse {
+    pub fn write(&self, body: &mut Vec<u8>) {
+        body.extend_from_slice(&0i32.to_be_bytes()); // throttle_time_ms
+        body.push((self.topics.len() + 1) as u8);
+        for topic in &self.topics {
+            write_compact_string(body, &topic.name);
+
+            body.push((topic.partitions.len() + 1) as u8);
+            for partition in &topic.partitions {
+                body.extend_from_slice(&partition.partition_index.to_be_bytes());
+                body.extend_from_slice(&partition.error_code.to_be_bytes());
+                body.push(0); // tagged fields
+            }
+            body.push(0); // tagged fields
+        }
+        body.push(0); // tagged fields (top-level)
+    }
+}
+
+pub fn handle_offset_commit(
+    request: &OffsetCommitRequest,
+    store: &dyn OffsetStore,
+) -> OffsetCommitResponse {
+    let topics = request
+        .topics
+        .iter()
+        .map(|topic| {
+            let partitions = topic
+                .partitions
+                .iter()
+                .map(|partition| {
+                    store.commit(
+                        &request.group_id,
+                        &topic.name,
+                        partition.partition_index,
+                        partition.committed_offset,
+                        partition.committed_metadata.clone(),
+                    );
+
+                    OffsetCommitPartitionResult {
+                        partition_index: partition.partition_index,
+                        error_code: error_codes::NONE,
+                    }
+                })
+                .collect();
+            OffsetCommitTopicResult {
+                name: topic.name.clone(),
+                partitions,
+            }
+        })
+        .collect();
+
+    OffsetCommitResponse { topics }
+}
+
+// --- OffsetFetch --------------------------------------------------------------------
+
+pub struct OffsetFetchTopic {
+    pub name: String,
+    pub partition_indexes: Vec<i32>,
+}
+
+pub struct OffsetFetchRequest {
+    pub group_id: String,
+    pub topics: Vec<OffsetFetchTopic>,
+}
+
+pub fn parse_offset_fetch_request(body: &[u8]) -> Result<OffsetFetchRequest, KafkaError> {
+    let mut cursor = Cursor::new(body);
+
+    let group_id = read_compact_string(&mut cursor)?;
+
+    let topics = read_compact_array(&mut cursor, |cursor| {
+        let name = read_compact_string(cursor)?;
+        let partition_indexes = read_compact_array(cursor, read_int32)?;
+        read_tagged_fields(cursor)?;
+        Ok(OffsetFetchTopic {
+            name,
+            partition_indexes,
+        })
+    })?;
+
+    read_tagged_fields(&mut cursor)?;
+
+    Ok(OffsetFetchRequest { group_id, topics })
+}
+
+pub struct OffsetFetchPartitionResult {
+    pub partition_index: i32,
+    pub committed_offset: i64,
+    pub committed_leader_epoch: i32,
+    pub metadata: Option<String>,
+    pub error_code: i16,
+}
+
+pub struct OffsetFetchTopicResult {
+    pub name: String,
+    pub partitions: Vec<OffsetFetchPartitionResult>,
+}
+
+pub struct OffsetFetchResponse {
+    pub topics: Vec<OffsetFetchTopicResult>,
+    pub error_code: i16,
+}
+
+impl OffsetFetchResponse {
+    pub fn write(&self, body: &mut Vec<u8>) {
+        body.extend_from_slice(&0i32.to_be_bytes()); // throttle_time_ms
+        body.push((self.topics.len() + 1) as u8);
+        for topic in &self.topics {
+            write_compact_string(body, &topic.name);
+
+            body.push((topic.partitions.len() + 1) as u8);
+            for partition in &topic.partitions {
+                body.extend_from_slice(&partition.partition_index.to_be_bytes());
+                body.extend_from_slice(&partition.committed_offset.to_be_bytes());
+                body.extend_from_slice(&partition.committed_leader_epoch.to_be_bytes());
+                write_compact_nullable_string(body, partition.metadata.as_deref());
+                body.extend_from_slice(&partition.error_code.to_be_bytes());
+                body.push(0); // tagged fields
+            }
+            body.push(0); // tagged fields
+        }
+        body.extend_from_slice(&self.error_code.to_be_bytes()); // top-level error_code
+        body.push(0); // tagged fields (top-level)
+    }
+}
+
+pub fn handle_offset_fetch(
+    request: &OffsetFetchRequest,
+    store: &dyn OffsetStore,
+) -> OffsetFetchResponse {
+    let topics = request
+        .topics
+        .iter()
+        .map(|topic| {
+            let partitions = topic
+                .partition_indexes
+                .iter()
+                .map(|&partition_index| {
+                    let (committed_offset, metadata) = store
+                        .fetch(&request.group_id, &topic.name, partition_index)
+                        .unwrap_or((-1, None));
+
+                    OffsetFetchPartitionResult {
+                        partition_index,
+                        committed_offset,
+                        committed_leader_epoch: -1,
+                        metadata,
+                        error_code: error_codes::NONE,
+                    }
+                })
+                .collect();
+            OffsetFetchTopicResult {
+                name: topic.name.clone(),
+                partitions,
+            }
+        })
+        .collect();
+
+    OffsetFetchResponse {
+        topics,
+        error_code: error_codes::NONE,
+    }
+}
+
+pub struct FindCoordinatorApi;
+
+impl KafkaApi for FindCoordinatorApi {
+    fn api_key(&self) -> i16 {
+        10
+    }
+
+    fn min_version(&self) -> i16 {
+        3
+    }
+
+    fn max_version(&self) -> i16 {
+        3
+    }
+
+    fn handle(&self, request: &Request, _ctx: &Context) -> ApiResponse {
+        let response = match parse_find_coordinator_request(&request.body) {
+            Ok(fc_request) => build_find_coordinator_response(&fc_request),
+            Err(e) => {
+                println!("failed to parse find_coordinator request: {}", e);
+                FindCoordinatorResponse {
+                    error_code: error_codes::UNSUPPORTED_VERSION,
+                    node_id: -1,
+                    host: String::new(),
+                    port: -1,
+                }
+            }
+        };
+
+        ApiResponse::FindCoordinator(response)
+    }
+}
+
+pub struct OffsetCommitApi;
+
+impl KafkaApi for OffsetCommitApi {
+    fn api_key(&self) -> i16 {
+        8
+    }
+
+    fn min_version(&self) -> i16 {
+        8
+    }
+
+    fn max_version(&self) -> i16 {
+        8
+    }
+
+    fn handle(&self, request: &Request, ctx: &Context) -> ApiResponse {
+        let response = match parse_offset_commit_request(&request.body) {
+            Ok(oc_request) => handle_offset_commit(&oc_request, ctx.offsets.as_ref()),
+            Err(e) => {
+                println!("failed to parse offset_commit request: {}", e);
+                OffsetCommitResponse { topics: Vec::new() }
+            }
+        };
+
+        ApiResponse::OffsetCommit(response)
+    }
+}
+
+pub struct OffsetFetchApi;
+
+impl KafkaApi for OffsetFetchApi {
+    fn api_key(&self) -> i16 {
+        9
+    }
+
+    fn min_version(&self) -> i16 {
+        6
+    }
+
+    fn max_version(&self) -> i16 {
+        6
+    }
+
+    fn handle(&self, request: &Request, ctx: &Context) -> ApiResponse {
+        let response = match parse_offset_fetch_request(&request.body) {
+            Ok(of_request) => handle_offset_fetch(&of_request, ctx.offsets.as_ref()),
+            Err(e) => {
+                println!("failed to parse offset_fetch request: {}", e);
+                OffsetFetchResponse {
+                    topics: Vec::new(),
+                    error_code: error_codes::UNSUPPORTED_VERSION,
+                }
+            }
+        };
+
+        ApiResponse::OffsetFetch(response)
+    }
+}
@@ -0,0 +1,52 @@
+// Cluster metadata backing DescribeTopicPartitions (and, later, any other API that needs
+// to know what topics/partitions exist). Kept behind a trait so the in-memory store used
+// today can be swapped for something backed by the real `__cluster_metadata` log later.
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+#[derive(Clone)]
+pub struct PartitionMetadata {
+    pub index: i32,
+    pub leader_id: i32,
+    pub leader_epoch: i32,
+    pub replica_nodes: Vec<i32>,
+    pub isr_nodes: Vec<i32>,
+    pub eligible_leader_replicas: Vec<i32>,
+}
+
+#[derive(Clone)]
+pub struct TopicMetadata {
+    pub name: String,
+    pub uuid: [u8; 16],
+    pub is_internal: bool,
+    pub partitions: Vec<PartitionMetadata>,
+}
+
+pub trait MetadataStore: Send + Sync {
+    fn topic(&self, name: &str) -> Option<TopicMetadata>;
+}
+
+// Default implementation: an in-memory map seeded once at startup. Empty until topics
+// are registered, since this server doesn't read a metadata log (yet).
+#[derive(Default)]
+pub struct InMemoryMetadataStore {
+    topics: RwLock<HashMap<String, TopicMetadata>>,
+}
+
+impl InMemoryMetadataStore {
+    pub fn seeded(topics: Vec<TopicMetadata>) -> Self {
+        let mut map = HashMap::with_capacity(topics.len());
+        for topic in topics {
+            map.insert(topic.name.clone(), topic);
+        }
+        InMemoryMetadataStore {
+            topics: RwLock::new(map),
+        }
+    }
+}
+
+impl MetadataStore for InMemoryMetadataStore {
+    fn topic(&self, name: &str) -> Option<TopicMetadata> {
+        self.topics.read().unwrap().get(name).cloned()
+    }
+}
@@ -0,0 +1,320 @@
+// Produce (api_key 0): accepts a v2 record batch per partition, validates its CRC and
+// reports back whether each partition accepted the write.
+use std::io::Cursor;
+
+use crate::api::{ApiResponse, KafkaApi};
+use crate::codec::Request;
+use crate::context::Context;
+use crate::crc32c::crc32c;
+use crate::error::KafkaError;
+use crate::error_codes;
+use crate::readers::{
+    read_compact_array, read_compact_bytes, read_compact_nullable_string, read_compact_string,
+    read_int16, read_int32, read_int64, read_nullable_string, read_tagged_fields, read_varlong,
+    write_compact_string,
+};
+
+pub struct ProducePartitionData {
+    pub index: i32,
+    pub records: Vec<u8>, // Raw bytes of the v2 record batch, parsed by parse_record_batch_header and parse_records
+}
+
+pub struct ProduceTopicData {
+    pub name: String,
+    pub partitions: Vec<ProducePartitionData>,
+}
+
+pub struct ProduceRequest {
+    pub acks: i16,
+    pub timeout_ms: i32,
+    pub topics: Vec<ProduceTopicData>,
+}
+
+// A parsed v2 RecordBatch header (see KIP-98 / the Kafka message format docs). We only
+// need enough of it to validate the CRC and report back a base offset.
+pub struct RecordBatchHeader {
+    pub base_offset: i64,
+    pub batch_length: i32,
+    pub magic: i8,
+    pub crc: u32,
+    pub record_count: i32,
+}
+
+pub fn parse_produce_request(body: &[u8], flexible: bool) -> Result<ProduceRequest, KafkaError> {
+    let mut cursor = Cursor::new(body);
+
+    if flexible {
+        let _transactional_id = read_compact_nullable_string(&mut cursor)?;
+    } else {
+        let _transactional_id = read_nullable_string(&mut cursor)?;
+    }
+
+    let acks = read_int16(&mut cursor)?;
+    let timeout_ms = read_int32(&mut cursor)?;
+
+    let topics = if flexible {
+        read_compact_array(&mut cursor, |cursor| {
+            let name = read_compact_string(cursor)?;
+            let partitions = read_compact_array(cursor, |cursor| {
+                let index = read_int32(cursor)?;
+                let records = read_compact_bytes(cursor)?;
+                read_tagged_fields(cursor)?;
+                Ok(ProducePartitionData { index, records })
+            })?;
+            read_tagged_fields(cursor)?;
+            Ok(ProduceTopicData { name, partitions })
+        })?
+    } else {
+        return Err(KafkaError::UnsupportedRequest(
+            "Produce below v9 (non-flexible) is not implemented",
+        ));
+    };
+
+    read_tagged_fields(&mut cursor)?;
+
+    Ok(ProduceRequest {
+        acks,
+        timeout_ms,
+        topics,
+    })
+}
+
+// Parses the v2 RecordBatch header and validates the stored CRC against a fresh CRC32C
+// computed over everything after the crc field itself.
+pub fn parse_record_batch_header(data: &[u8]) -> Result<RecordBatchHeader, KafkaError> {
+    let mut cursor = Cursor::new(data);
+
+    let base_offset = read_int64(&mut cursor)?;
+    let batch_length = read_int32(&mut cursor)?;
+    let _partition_leader_epoch = read_int32(&mut cursor)?;
+
+    let mut magic_buf = [0u8; 1];
+    std::io::Read::read_exact(&mut cursor, &mut magic_buf)?;
+    let magic = magic_buf[0] as i8;
+
+    let crc = read_int32(&mut cursor)? as u32;
+
+    // batch_length counts everything from partition_leader_epoch onward, so the crc
+    // payload (attributes through the records) is batch_length minus the
+    // partition_leader_epoch(4) + magic(1) + crc(4) bytes that precede it. Bounding the
+    // slice this way (instead of reading to the end of `data`) keeps the checksum from
+    // running over any batch that follows this one in the same partition's byte buffer.
+    let crc_payload_start = cursor.position() as usize;
+    let crc_payload_len =
+        (batch_length as usize)
+            .checked_sub(4 + 1 + 4)
+            .ok_or(KafkaError::CrcMismatch {
+                expected: crc,
+                computed: 0,
+            })?;
+    let crc_payload_end = crc_payload_start
+        .checked_add(crc_payload_len)
+        .filter(|&end| end <= data.len())
+        .ok_or(KafkaError::CrcMismatch {
+            expected: crc,
+            computed: 0,
+        })?;
+    let computed = crc32c(&data[crc_payload_start..crc_payload_end]);
+
+    if computed != crc {
+        return Err(KafkaError::CrcMismatch {
+            expected: crc,
+            computed,
+        });
+    }
+
+    let _attributes = read_int16(&mut cursor)?;
+    let _last_offset_delta = read_int32(&mut cursor)?;
+    let _first_timestamp = read_int64(&mut cursor)?;
+    let _max_timestamp = read_int64(&mut cursor)?;
+    let _producer_id = read_int64(&mut cursor)?;
+    let _producer_epoch = read_int16(&mut cursor)?;
+    let _base_sequence = read_int32(&mut cursor)?;
+    let record_count = read_int32(&mut cursor)?;
+
+    Ok(RecordBatchHeader {
+        base_offset,
+        batch_length,
+        magic,
+        crc,
+        record_count,
+    })
+}
+
+// A single record inside a batch, after varint/zigzag fields have been decoded.
+pub struct Record {
+    pub key: Option<Vec<u8>>,
+    pub value: Option<Vec<u8>>,
+}
+
+pub fn parse_records(data: &[u8], record_count: i32) -> Result<Vec<Record>, KafkaError> {
+    let mut cursor = Cursor::new(data);
+    let mut records = Vec::with_capacity(record_count.max(0) as usize);
+
+    for _ in 0..record_count {
+        let _length = read_varlong(&mut cursor)?;
+        let mut attributes = [0u8; 1];
+        std::io::Read::read_exact(&mut cursor, &mut attributes)?;
+        let _timestamp_delta = read_varlong(&mut cursor)?;
+        let _offset_delta = read_varlong(&mut cursor)?;
+
+        let key_len = read_varlong(&mut cursor)?;
+        let key = read_varint_sized_bytes(&mut cursor, key_len)?;
+
+        let value_len = read_varlong(&mut cursor)?;
+        let value = read_varint_sized_bytes(&mut cursor, value_len)?;
+
+        let header_count = read_varlong(&mut cursor)?;
+        for _ in 0..header_count {
+            let key_len = read_varlong(&mut cursor)?;
+            read_varint_sized_bytes(&mut cursor, key_len)?;
+            let value_len = read_varlong(&mut cursor)?;
+            read_varint_sized_bytes(&mut cursor, value_len)?;
+        }
+
+        records.push(Record { key, value });
+    }
+
+    Ok(records)
+}
+
+// A varint length of -1 means a null key/value; otherwise it's the byte length to read.
+fn read_varint_sized_bytes(
+    cursor: &mut Cursor<&[u8]>,
+    length: i64,
+) -> Result<Option<Vec<u8>>, KafkaError> {
+    if length < 0 {
+        return Ok(None);
+    }
+
+    let mut buf = vec![0u8; length as usize];
+    std::io::Read::read_exact(cursor, &mut buf)?;
+    Ok(Some(buf))
+}
+
+pub struct ProducePartitionResult {
+    pub index: i32,
+    pub error_code: i16,
+    pub base_offset: i64,
+}
+
+pub struct ProduceTopicResult {
+    pub name: String,
+    pub partitions: Vec<ProducePartitionResult>,
+}
+
+pub struct ProduceResponse {
+    pub topics: Vec<ProduceTopicResult>,
+}
+
+impl ProduceResponse {
+    // Flexible (v9+) body: for each topic/partition, an error code, base offset and the
+    // later per-partition fields (log_append_time_ms, log_start_offset, record_errors,
+    // error_message).
+    pub fn write(&self, body: &mut Vec<u8>) {
+        body.push((self.topics.len() + 1) as u8);
+
+        for topic in &self.topics {
+            write_compact_string(body, &topic.name);
+
+            body.push((topic.partitions.len() + 1) as u8);
+            for partition in &topic.partitions {
+                body.extend_from_slice(&partition.index.to_be_bytes());
+                body.extend_from_slice(&partition.error_code.to_be_bytes());
+                body.extend_from_slice(&partition.base_offset.to_be_bytes());
+                body.extend_from_slice(&(-1i64).to_be_bytes()); // log_append_time_ms
+                body.extend_from_slice(&(-1i64).to_be_bytes()); // log_start_offset
+                body.push(1); // record_errors: empty compact array
+                body.push(0); // error_message: null
+                body.push(0); // tagged fields
+            }
+            body.push(0); // tagged fields
+        }
+
+        body.extend_from_slice(&0i32.to_be_bytes()); // throttle_time_ms
+        body.push(0); // tagged fields
+    }
+}
+
+// Validates every partition's record batch and maps the outcome to a Produce error code.
+pub fn handle_produce(request: &ProduceRequest) -> ProduceResponse {
+    let topics = request
+        .topics
+        .iter()
+        .map(|topic| {
+            let partitions = topic
+                .partitions
+                .iter()
+                .map(|partition| {
+                    let (error_code, base_offset) = validate_partition(&partition.records);
+                    ProducePartitionResult {
+                        index: partition.index,
+                        error_code,
+                        base_offset,
+                    }
+                })
+                .collect();
+            ProduceTopicResult {
+                name: topic.name.clone(),
+                partitions,
+            }
+        })
+        .collect();
+
+    ProduceResponse { topics }
+}
+
+// Returns the partition's error code and the base offset to report back (-1 if the
+// batch was rejected and there's no meaningful offset to give).
+fn validate_partition(records: &[u8]) -> (i16, i64) {
+    let header = match parse_record_batch_header(records) {
+        Ok(header) => header,
+        Err(_) => return (error_codes::CORRUPT_MESSAGE, -1),
+    };
+
+    if header.magic != 2 {
+        return (error_codes::CORRUPT_MESSAGE, -1);
+    }
+
+    // Records live after the fixed batch header; parsing them checks that `record_count`
+    // well-formed records are actually on the wire. Bytes after the last one are not checked.
+    let records_start = RECORD_BATCH_HEADER_LEN;
+    if parse_records(&records[records_start..], header.record_count).is_err() {
+        return (error_codes::CORRUPT_MESSAGE, -1);
+    }
+
+    (error_codes::NONE, header.base_offset)
+}
+
+// baseOffset(8) + batchLength(4) + partitionLeaderEpoch(4) + magic(1) + crc(4) +
+// attributes(2) + lastOffsetDelta(4) + firstTimestamp(8) + maxTimestamp(8) +
+// producerId(8) + producerEpoch(2) + baseSequence(4) + recordCount(4)
+const RECORD_BATCH_HEADER_LEN: usize = 8 + 4 + 4 + 1 + 4 + 2 + 4 + 8 + 8 + 8 + 2 + 4 + 4;
+
+pub struct ProduceApi;
+
+impl KafkaApi for ProduceApi {
+    fn api_key(&self) -> i16 {
+        0
+    }
+
+    fn min_version(&self) -> i16 {
+        9
+    }
+
+    fn max_version(&self) -> i16 {
+        9
+    }
+
+    fn handle(&self, request: &Request, _ctx: &Context) -> ApiResponse {
+        let response = match parse_produce_request(&request.body, true) {
+            Ok(produce_request) => handle_produce(&produce_request),
+            Err(e) => {
+                println!("failed to parse produce request: {}", e);
+                ProduceResponse { topics: Vec::new() }
+            }
+        };
+
+        ApiResponse::Produce(response)
+    }
+}
@@ -1,6 +1,6 @@
 // Import required modules for reading binary data
+use crate::error::KafkaError; // Import our custom error type
 use std::io::{Cursor, Read}; // Cursor for in-memory reading, Read trait for reading operations
-use crate::KafkaError; // Import our custom error type from main module
 
 // Function to read a 16-bit signed integer from a cursor in big-endian format
 // Used for reading API keys, API versions, error codes, etc.
@@ -18,32 +18,220 @@ pub fn read_int32(cursor: &mut Cursor<&[u8]>) -> Result<i32, KafkaError> {
     Ok(i32::from_be_bytes(buf)) // Convert big-endian bytes to i32 and return
 }
 
+// Function to read a 64-bit signed integer from a cursor in big-endian format
+// Used for offsets, timestamps, producer IDs, etc.
+pub fn read_int64(cursor: &mut Cursor<&[u8]>) -> Result<i64, KafkaError> {
+    let mut buf = [0u8; 8];
+    cursor.read_exact(&mut buf)?;
+    Ok(i64::from_be_bytes(buf))
+}
+
 // Function to read a nullable string from a cursor according to Kafka protocol
 // Kafka strings are prefixed with a 16-bit length field
 // A length of -1 indicates a null string
 pub fn read_nullable_string(cursor: &mut Cursor<&[u8]>) -> Result<Option<String>, KafkaError> {
     // First, read the 16-bit length prefix
     let length = read_int16(cursor)?;
-    
+
     // Check for null string indicator
     if length == -1 {
         return Ok(None); // Return None for null strings
     }
-    
+
     // Validate string length - negative values (except -1) are invalid
     if length < 0 {
         return Err(KafkaError::InvalidStringLength(length));
     }
-    
+
     // Allocate buffer for string data based on length
     let mut buf = vec![0u8; length as usize];
-    
+
     // Read the string bytes from cursor
     cursor.read_exact(&mut buf)?;
-    
+
     // Convert bytes to UTF-8 string (may fail if invalid UTF-8)
     let string = String::from_utf8(buf)?;
-    
+
     // Return the parsed string wrapped in Some
     Ok(Some(string))
 }
+
+// Function to read a KIP-482 unsigned varint (LEB128, little-endian groups of 7 bits)
+// Each byte contributes its low 7 bits; the high bit set means "more bytes follow"
+pub fn read_unsigned_varint(cursor: &mut Cursor<&[u8]>) -> Result<u32, KafkaError> {
+    let mut value: u32 = 0;
+    let mut shift = 0;
+
+    loop {
+        // A varint encoding an i32/u32 never needs more than 5 bytes (35 bits)
+        if shift >= 35 {
+            return Err(KafkaError::InvalidVarint);
+        }
+
+        let mut byte = [0u8; 1];
+        cursor.read_exact(&mut byte)?;
+        let byte = byte[0];
+
+        value |= ((byte & 0x7f) as u32) << shift;
+
+        // High bit clear means this was the last byte of the varint
+        if byte & 0x80 == 0 {
+            break;
+        }
+
+        shift += 7;
+    }
+
+    Ok(value)
+}
+
+// Function to read an unsigned varlong: the same LEB128 encoding as read_unsigned_varint,
+// but accumulated into a u64 and capped at 10 bytes (70 bits) instead of 5 (35 bits), since
+// record-batch fields are allowed the full 64-bit range.
+fn read_unsigned_varlong(cursor: &mut Cursor<&[u8]>) -> Result<u64, KafkaError> {
+    let mut value: u64 = 0;
+    let mut shift = 0;
+
+    loop {
+        if shift >= 70 {
+            return Err(KafkaError::InvalidVarint);
+        }
+
+        let mut byte = [0u8; 1];
+        cursor.read_exact(&mut byte)?;
+        let byte = byte[0];
+
+        value |= ((byte & 0x7f) as u64) << shift;
+
+        if byte & 0x80 == 0 {
+            break;
+        }
+
+        shift += 7;
+    }
+
+    Ok(value)
+}
+
+// Function to read a zigzag-encoded signed varint (at most 5 bytes / 35 bits). Decodes
+// the same LEB128 bytes as read_unsigned_varint, then zigzag-decodes: the sign bit is
+// stored in bit 0 instead of the top bit. Compact-string/array lengths are unsigned and
+// use read_unsigned_varint instead.
+pub fn read_varint(cursor: &mut Cursor<&[u8]>) -> Result<i64, KafkaError> {
+    let raw = read_unsigned_varint(cursor)? as i64;
+    Ok((raw >> 1) ^ -(raw & 1))
+}
+
+// Function to read a zigzag-encoded signed varlong, as used by record-batch fields
+// (record length, timestamp/offset deltas, key/value lengths, header counts). Unlike
+// read_varint, this allows the full 64-bit range instead of capping at 35 bits.
+pub fn read_varlong(cursor: &mut Cursor<&[u8]>) -> Result<i64, KafkaError> {
+    let raw = read_unsigned_varlong(cursor)? as i64;
+    Ok((raw >> 1) ^ -(raw & 1))
+}
+
+// Function to read a compact string (flexible versions): a uvarint length of `n`, where
+// `n == 0` means null and the actual byte length is `n - 1`
+pub fn read_compact_nullable_string(
+    cursor: &mut Cursor<&[u8]>,
+) -> Result<Option<String>, KafkaError> {
+    let length = read_unsigned_varint(cursor)?;
+
+    if length == 0 {
+        return Ok(None); // Zero means null for compact strings
+    }
+
+    let mut buf = vec![0u8; (length - 1) as usize];
+    cursor.read_exact(&mut buf)?;
+
+    Ok(Some(String::from_utf8(buf)?))
+}
+
+// Same as read_compact_nullable_string, but the caller knows the field can't be null
+pub fn read_compact_string(cursor: &mut Cursor<&[u8]>) -> Result<String, KafkaError> {
+    match read_compact_nullable_string(cursor)? {
+        Some(string) => Ok(string),
+        None => Err(KafkaError::InvalidStringLength(-1)),
+    }
+}
+
+// Function to read a compact array: a uvarint length of `n` (0 means an empty/absent array,
+// the real element count is `n - 1`), followed by that many elements decoded by `read_element`
+pub fn read_compact_array<T, F>(
+    cursor: &mut Cursor<&[u8]>,
+    mut read_element: F,
+) -> Result<Vec<T>, KafkaError>
+where
+    F: FnMut(&mut Cursor<&[u8]>) -> Result<T, KafkaError>,
+{
+    let length = read_unsigned_varint(cursor)?;
+
+    if length == 0 {
+        return Ok(Vec::new());
+    }
+
+    let count = (length - 1) as usize;
+    let mut items = Vec::with_capacity(count);
+
+    for _ in 0..count {
+        items.push(read_element(cursor)?);
+    }
+
+    Ok(items)
+}
+
+// Function to read compact bytes: a uvarint length of `n` (0 means empty), followed by
+// `n - 1` raw bytes. Used for fields like the Produce request's per-partition records.
+pub fn read_compact_bytes(cursor: &mut Cursor<&[u8]>) -> Result<Vec<u8>, KafkaError> {
+    let length = read_unsigned_varint(cursor)?;
+
+    if length == 0 {
+        return Ok(Vec::new());
+    }
+
+    let mut buf = vec![0u8; (length - 1) as usize];
+    cursor.read_exact(&mut buf)?;
+    Ok(buf)
+}
+
+// Function to write a compact string (flexible versions): a uvarint length of `n`,
+// where `n` is the byte length plus one (the compact-array/string convention, so `0`
+// is reserved for null/empty).
+pub fn write_compact_string(buf: &mut Vec<u8>, value: &str) {
+    let mut length = (value.len() + 1) as u32;
+    while length >= 0x80 {
+        buf.push((length as u8 & 0x7f) | 0x80);
+        length >>= 7;
+    }
+    buf.push(length as u8);
+    buf.extend_from_slice(value.as_bytes());
+}
+
+// Same as write_compact_string, but `None` writes the zero-length marker instead.
+pub fn write_compact_nullable_string(buf: &mut Vec<u8>, value: Option<&str>) {
+    match value {
+        None => buf.push(0),
+        Some(value) => write_compact_string(buf, value),
+    }
+}
+
+// Function to read the tagged fields buffer that terminates every flexible-version
+// struct: a uvarint tag count, then for each tag a uvarint tag number and a uvarint
+// size, followed by that many raw bytes. We don't know about any tags yet, so they are
+// preserved as-is for whoever cares about them later.
+pub fn read_tagged_fields(cursor: &mut Cursor<&[u8]>) -> Result<Vec<(u32, Vec<u8>)>, KafkaError> {
+    let tag_count = read_unsigned_varint(cursor)?;
+    let mut fields = Vec::with_capacity(tag_count as usize);
+
+    for _ in 0..tag_count {
+        let tag = read_unsigned_varint(cursor)?;
+        let size = read_unsigned_varint(cursor)?;
+
+        let mut buf = vec![0u8; size as usize];
+        cursor.read_exact(&mut buf)?;
+
+        fields.push((tag, buf));
+    }
+
+    Ok(fields)
+}